@@ -1,7 +1,8 @@
 //! Missing adapters connecting `bytes` to `std::io`.
 
 use bytes::{Buf, BufMut};
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
+use std::mem::MaybeUninit;
 
 /// A type that implements [`BufMut`] and sends its output to an underlying
 /// [`io::Write`].
@@ -48,6 +49,189 @@ impl<W: Write> BufMutWriter<W> {
         self.buf.clear();
     }
 
+    /// Returns the number of bytes that can currently be written into `buf`
+    /// without reallocating.
+    fn spare_capacity(&self) -> usize {
+        self.buf.capacity() - self.buf.len()
+    }
+
+    /// Copies `src` into the buffer's spare capacity and extends `buf` to
+    /// cover it, without the bounds checks and incremental length updates
+    /// that `Vec::extend_from_slice` performs.
+    ///
+    /// The caller must ensure `src.len() <= self.spare_capacity()`.
+    fn write_to_buffer_unchecked(&mut self, src: &[u8]) {
+        debug_assert!(src.len() <= self.spare_capacity());
+        let len = self.buf.len();
+        // SAFETY: the caller guarantees `src.len() <= spare_capacity()`, so
+        // `len + src.len()` is within `buf`'s allocation, and the bytes
+        // being written are about to be included in `buf`'s initialized
+        // length.
+        unsafe {
+            std::ptr::copy_nonoverlapping(src.as_ptr(), self.buf.as_mut_ptr().add(len), src.len());
+            self.buf.set_len(len + src.len());
+        }
+    }
+
+    /// Writes a fixed-size, `N`-byte window directly into the buffer's
+    /// spare capacity, for leaf encodings (varints, LEB128, fixed-width
+    /// integers, length prefixes) that know their maximum size up front.
+    ///
+    /// `N` should be small relative to the buffer's capacity. If there
+    /// isn't room for `N` more bytes, the buffer is flushed first; if `N`
+    /// is larger than the writer's configured capacity (unusual, but not
+    /// an error), the buffer is grown (with `Vec::reserve`, so possibly
+    /// beyond `N`) to fit it rather than panicking. `f` is then given a `&mut [MaybeUninit<u8>; N]` pointing
+    /// into that spare capacity and must return how many of the `N` bytes
+    /// it actually wants to keep; the buffer's length is advanced by that
+    /// many bytes.
+    ///
+    /// The array handed to `f` is not guaranteed to be initialized — it
+    /// may be uninitialized spare capacity, or stale bytes left over from
+    /// a previous write — so `f` must itself initialize every byte (up to
+    /// the count it returns) that it wants to keep, e.g. via
+    /// `MaybeUninit::write`.
+    pub fn write_with<const N: usize>(
+        &mut self,
+        f: impl FnOnce(&mut [MaybeUninit<u8>; N]) -> usize,
+    ) {
+        if self.spare_capacity() < N {
+            self.flush_buf();
+        }
+        if self.spare_capacity() < N {
+            self.buf.reserve(N);
+        }
+
+        let len = self.buf.len();
+        // SAFETY: `len + N <= buf.capacity()`, so this window lies within
+        // `buf`'s allocation and is disjoint from its initialized prefix.
+        // `MaybeUninit<u8>` has the same layout as `u8` but, unlike `u8`,
+        // doesn't require its contents to be initialized, which honestly
+        // reflects that this spare capacity may not be.
+        let window =
+            unsafe { &mut *(self.buf.as_mut_ptr().add(len).cast::<[MaybeUninit<u8>; N]>()) };
+        let used = f(window);
+        assert!(
+            used <= N,
+            "write_with: closure reported writing more bytes than it was given"
+        );
+        // SAFETY: the first `used` bytes of `window` were just initialized
+        // by `f`, and `len + used <= len + N <= buf.capacity()`.
+        unsafe {
+            self.buf.set_len(len + used);
+        }
+    }
+
+    /// Copies all remaining bytes from `reader` into the underlying writer,
+    /// reusing this writer's own internal buffer as the transfer buffer
+    /// (the way `std::io::copy` specializes for a `BufWriter`), so no extra
+    /// heap buffer is allocated. The effective copy block size is whatever
+    /// capacity this `BufMutWriter` was created with.
+    ///
+    /// Returns the total number of bytes copied. Errors from reading and
+    /// from the buffered write are both reported through the returned
+    /// `io::Result`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this writer's capacity is zero: reading into a
+    /// zero-length spare-capacity slice would return `Ok(0)` from `Read`
+    /// with no EOF implication, which `copy_from` can't tell apart from
+    /// true EOF.
+    pub fn copy_from<R: Read>(&mut self, reader: &mut R) -> io::Result<u64> {
+        assert!(
+            self.buf.capacity() > 0,
+            "copy_from: BufMutWriter capacity must be greater than zero"
+        );
+        let mut total = 0u64;
+        loop {
+            if self.buf.len() == self.buf.capacity() {
+                self.flush_buf();
+                self.check()?;
+            }
+
+            let spare = self.buf.spare_capacity_mut();
+            // SAFETY: `Read::read`'s contract requires implementations to
+            // only ever write into the slice they are given and forbids
+            // them from relying on any property of its initial contents,
+            // so it's sound to hand it this spare capacity reinterpreted
+            // as `&mut [u8]` for the duration of the call, even though
+            // those bytes aren't initialized yet. This is the same
+            // read-into-uninitialized-memory pattern used throughout the
+            // ecosystem for buffered reads (see `Read::read`'s docs).
+            let spare = unsafe {
+                std::slice::from_raw_parts_mut(spare.as_mut_ptr().cast::<u8>(), spare.len())
+            };
+            // Retry on `Interrupted`, same as `std::io::copy` and the rest
+            // of std's generic I/O code, instead of aborting the transfer
+            // on a signal that a well-behaved `Read` impl may legitimately
+            // surface.
+            let n = loop {
+                match reader.read(spare) {
+                    Ok(n) => break n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                }
+            };
+            if n == 0 {
+                break;
+            }
+
+            let len = self.buf.len();
+            // SAFETY: `read` just initialized the first `n` bytes of `spare`.
+            unsafe {
+                self.buf.set_len(len + n);
+            }
+            total += n as u64;
+        }
+
+        self.flush_buf();
+        self.check()?;
+        Ok(total)
+    }
+
+    /// Writes the decimal representation of `v`, straight into the
+    /// buffer instead of going through `write!`/`core::fmt`.
+    pub fn put_u64_decimal(&mut self, mut v: u64) {
+        let mut scratch = [0u8; 20]; // u64::MAX has 20 decimal digits
+        let mut i = scratch.len();
+        loop {
+            i -= 1;
+            scratch[i] = b'0' + (v % 10) as u8;
+            v /= 10;
+            if v == 0 {
+                break;
+            }
+        }
+        self.put_slice(&scratch[i..]);
+    }
+
+    /// Writes `data` as a lowercase hex dump, two ASCII characters per
+    /// input byte, straight into the buffer instead of going through
+    /// `write!`/`core::fmt`.
+    pub fn put_hex(&mut self, data: &[u8]) {
+        fn nibble(n: u8) -> u8 {
+            if n < 10 {
+                b'0' + n
+            } else {
+                b'a' + n - 10
+            }
+        }
+        for &byte in data {
+            self.write_with::<2>(|buf| {
+                buf[0].write(nibble(byte >> 4));
+                buf[1].write(nibble(byte & 0xf));
+                2
+            });
+        }
+    }
+
+    /// Writes `s` directly, bypassing `write!`/`core::fmt`'s formatting
+    /// machinery.
+    pub fn put_ascii(&mut self, s: &str) {
+        self.put_slice(s.as_bytes());
+    }
+
     pub fn close(mut self) -> io::Result<()> {
         self.flush_buf();
         self.check()
@@ -97,12 +281,12 @@ unsafe impl<W: Write> BufMut for BufMutWriter<W> {
     }
 
     fn put_slice(&mut self, src: &[u8]) {
-        if src.len() <= self.buf.capacity() - self.buf.len() {
-            self.buf.put_slice(src);
+        if src.len() <= self.spare_capacity() {
+            self.write_to_buffer_unchecked(src);
         } else {
             self.flush_buf();
             if src.len() < self.buf.capacity() {
-                self.buf.extend_from_slice(src);
+                self.write_to_buffer_unchecked(src);
             } else {
                 self.write(src);
             }
@@ -110,12 +294,341 @@ unsafe impl<W: Write> BufMut for BufMutWriter<W> {
     }
 }
 
+/// A type that implements [`Buf`] and pulls its input from an underlying
+/// [`io::Read`], the way [`io::BufReader`] pulls input for plain [`Read`].
+///
+/// [`Buf`]'s methods can't return I/O errors, so any error from the
+/// underlying reader is stored and surfaced later via [`check`](Self::check),
+/// exactly like [`BufMutWriter::check`].
+///
+/// Because [`Buf::chunk`] and [`Buf::remaining`] only take `&self`, the
+/// buffer is refilled eagerly: once in [`with_capacity`](Self::with_capacity)
+/// and again whenever [`advance`](Buf::advance) empties it, so that `chunk`
+/// never needs to perform I/O itself.
+///
+/// [`remaining`](Buf::remaining) only ever reports the bytes currently
+/// buffered, not the (unknown) total remaining in the underlying reader.
+/// Consumers that bounds-check a read against `remaining()` before issuing
+/// it — as `prost` does for length-delimited fields — need the buffer's
+/// capacity to be at least as large as the largest field or message they
+/// decode, or they may see a spurious "buffer underflow" even though more
+/// data was still available from the reader.
+pub struct BufReadBuf<R: Read> {
+    buf: Vec<u8>,
+    reader: R,
+    pos: usize,
+    filled: usize,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> BufReadBuf<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(reader, DEFAULT_CAPACITY)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero: `Read::read` with a zero-length
+    /// buffer is documented to return `Ok(0)` without distinguishing
+    /// "no buffer space" from true EOF, so a zero-capacity `BufReadBuf`
+    /// could never actually read anything, silently discarding all of the
+    /// reader's data.
+    pub fn with_capacity(reader: R, capacity: usize) -> Self {
+        assert!(capacity > 0, "BufReadBuf capacity must be greater than zero");
+        let mut this = BufReadBuf {
+            buf: vec![0; capacity],
+            reader,
+            pos: 0,
+            filled: 0,
+            error: None,
+        };
+        this.fill();
+        this
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    pub fn check(&mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn fill(&mut self) {
+        if self.error.is_some() {
+            return;
+        }
+        match self.reader.read(&mut self.buf) {
+            Ok(n) => {
+                self.pos = 0;
+                self.filled = n;
+            }
+            Err(e) => {
+                self.error = Some(e);
+                self.pos = 0;
+                self.filled = 0;
+            }
+        }
+    }
+}
+
+impl<R: Read> Buf for BufReadBuf<R> {
+    fn remaining(&self) -> usize {
+        self.filled - self.pos
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.filled - self.pos,
+            "cannot advance past the buffered data"
+        );
+        self.pos += cnt;
+        if self.pos == self.filled {
+            self.fill();
+        }
+    }
+}
+
+/// The error returned by [`Unpack::finish`] when the underlying slice ran
+/// out partway through a decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TruncatedError;
+
+impl std::fmt::Display for TruncatedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("truncated input: not enough bytes to decode the message")
+    }
+}
+
+impl std::error::Error for TruncatedError {}
+
+/// A non-panicking, truncation-safe reading view over a byte slice.
+///
+/// This is the decoding counterpart to [`BufMutWriter`]: where
+/// `BufMutWriter` is an encoding sink, `Unpack` is a decoding source for
+/// small, fixed-layout messages. No method ever panics on short input —
+/// the first read that would run past the end of the slice latches the
+/// view into an error state, after which that read and all subsequent
+/// reads return zero/empty defaults. Call [`is_ok`](Self::is_ok) or
+/// [`finish`](Self::finish) once done to find out whether the input was
+/// actually long enough. This mirrors the `structbuf` decoder philosophy:
+/// malformed or malicious input must not crash the program.
+pub struct Unpack<'a> {
+    data: &'a [u8],
+    pos: usize,
+    ok: bool,
+}
+
+impl<'a> Unpack<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Unpack {
+            data,
+            pos: 0,
+            ok: true,
+        }
+    }
+
+    /// Returns `false` once a read has run past the end of the slice.
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+
+    /// Consumes the view, reporting whether every read so far stayed
+    /// within the underlying slice.
+    pub fn finish(self) -> Result<(), TruncatedError> {
+        if self.ok {
+            Ok(())
+        } else {
+            Err(TruncatedError)
+        }
+    }
+
+    fn take(&mut self, n: usize) -> &'a [u8] {
+        if !self.ok || n > self.data.len() - self.pos {
+            self.ok = false;
+            return &[];
+        }
+        let slice = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        slice
+    }
+
+    pub fn u8(&mut self) -> u8 {
+        match self.take(1) {
+            [b] => *b,
+            _ => 0,
+        }
+    }
+
+    pub fn u16_le(&mut self) -> u16 {
+        match *self.take(2) {
+            [a, b] => u16::from_le_bytes([a, b]),
+            _ => 0,
+        }
+    }
+
+    pub fn u16_be(&mut self) -> u16 {
+        match *self.take(2) {
+            [a, b] => u16::from_be_bytes([a, b]),
+            _ => 0,
+        }
+    }
+
+    pub fn u32_le(&mut self) -> u32 {
+        match *self.take(4) {
+            [a, b, c, d] => u32::from_le_bytes([a, b, c, d]),
+            _ => 0,
+        }
+    }
+
+    pub fn u32_be(&mut self) -> u32 {
+        match *self.take(4) {
+            [a, b, c, d] => u32::from_be_bytes([a, b, c, d]),
+            _ => 0,
+        }
+    }
+
+    pub fn u64_le(&mut self) -> u64 {
+        match *self.take(8) {
+            [a, b, c, d, e, f, g, h] => u64::from_le_bytes([a, b, c, d, e, f, g, h]),
+            _ => 0,
+        }
+    }
+
+    pub fn u64_be(&mut self) -> u64 {
+        match *self.take(8) {
+            [a, b, c, d, e, f, g, h] => u64::from_be_bytes([a, b, c, d, e, f, g, h]),
+            _ => 0,
+        }
+    }
+
+    /// Reads `n` bytes. Returns an empty slice (and latches the error
+    /// state) if fewer than `n` bytes remain.
+    pub fn bytes(&mut self, n: usize) -> &'a [u8] {
+        self.take(n)
+    }
+}
+
+/// The error returned by [`Pack`]'s writer methods when a write would push
+/// the message past its configured maximum size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageTooLargeError {
+    pub max_len: usize,
+}
+
+impl std::fmt::Display for MessageTooLargeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "message exceeds the configured maximum of {} bytes", self.max_len)
+    }
+}
+
+impl std::error::Error for MessageTooLargeError {}
+
+/// A capacity-limited, panic-free encoding builder: the mirror image of
+/// [`Unpack`] for the write side of fixed-budget message framing.
+///
+/// Unlike [`BufMutWriter`], which always accepts more input and flushes it
+/// onward, `Pack` refuses to grow the message past a configured maximum
+/// size, returning [`MessageTooLargeError`] instead.
+pub struct Pack {
+    buf: Vec<u8>,
+    max_len: usize,
+}
+
+impl Pack {
+    pub fn new(max_len: usize) -> Self {
+        Pack {
+            buf: Vec::new(),
+            max_len,
+        }
+    }
+
+    pub fn with_capacity(max_len: usize, capacity: usize) -> Self {
+        Pack {
+            buf: Vec::with_capacity(capacity.min(max_len)),
+            max_len,
+        }
+    }
+
+    fn reserve(&mut self, n: usize) -> Result<(), MessageTooLargeError> {
+        if n > self.max_len - self.buf.len() {
+            return Err(MessageTooLargeError {
+                max_len: self.max_len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn u8(&mut self, v: u8) -> Result<(), MessageTooLargeError> {
+        self.reserve(1)?;
+        self.buf.push(v);
+        Ok(())
+    }
+
+    pub fn u16_le(&mut self, v: u16) -> Result<(), MessageTooLargeError> {
+        self.reserve(2)?;
+        self.buf.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn u16_be(&mut self, v: u16) -> Result<(), MessageTooLargeError> {
+        self.reserve(2)?;
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn u32_le(&mut self, v: u32) -> Result<(), MessageTooLargeError> {
+        self.reserve(4)?;
+        self.buf.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn u32_be(&mut self, v: u32) -> Result<(), MessageTooLargeError> {
+        self.reserve(4)?;
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn u64_le(&mut self, v: u64) -> Result<(), MessageTooLargeError> {
+        self.reserve(8)?;
+        self.buf.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn u64_be(&mut self, v: u64) -> Result<(), MessageTooLargeError> {
+        self.reserve(8)?;
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn bytes(&mut self, v: &[u8]) -> Result<(), MessageTooLargeError> {
+        self.reserve(v.len())?;
+        self.buf.extend_from_slice(v);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use prost::Message;
 
-    #[derive(prost::Message)]
+    #[derive(Clone, PartialEq, prost::Message)]
     struct MyMsg {
         #[prost(uint32, tag = "1")]
         a: u32,
@@ -163,4 +676,214 @@ mod tests {
     // - io errors are saved and delivered (not sure the API is friendly enough)
     // - io errors prevent subsequent writes
     // - check method
+
+    #[test]
+    fn test_write_with_grows_buffer_rather_than_panicking() {
+        // N (8) is bigger than the writer's configured capacity (4): this
+        // must grow the buffer instead of panicking.
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::with_capacity(&mut dest, 4);
+        write_buf.write_with::<8>(|buf| {
+            for (slot, byte) in buf.iter_mut().zip(1..=8u8) {
+                slot.write(byte);
+            }
+            8
+        });
+        write_buf.close().expect("no io::Errors from Vec<u8>");
+
+        assert_eq!(dest, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_copy_from_retries_on_interrupted() {
+        struct FlakyReader {
+            data: &'static [u8],
+            pos: usize,
+            interrupted_once: bool,
+        }
+
+        impl Read for FlakyReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if !self.interrupted_once {
+                    self.interrupted_once = true;
+                    return Err(io::Error::from(io::ErrorKind::Interrupted));
+                }
+                let n = std::cmp::min(buf.len(), self.data.len() - self.pos);
+                buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+                self.pos += n;
+                Ok(n)
+            }
+        }
+
+        let mut reader = FlakyReader {
+            data: b"hello world",
+            pos: 0,
+            interrupted_once: false,
+        };
+
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::new(&mut dest);
+        let copied = write_buf
+            .copy_from(&mut reader)
+            .expect("Interrupted should be retried, not propagated");
+        write_buf.close().expect("no io::Errors from Vec<u8>");
+
+        assert_eq!(copied, 11);
+        assert_eq!(dest, b"hello world");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than zero")]
+    fn test_copy_from_rejects_zero_capacity() {
+        let data = b"this data really exists and must not be silently dropped".to_vec();
+        let mut reader = &data[..];
+
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::with_capacity(&mut dest, 0);
+        let _ = write_buf.copy_from(&mut reader);
+    }
+
+    #[test]
+    fn test_buf_read_buf_small() {
+        let data = b"hello world".to_vec();
+        let mut read_buf = BufReadBuf::with_capacity(&data[..], 4);
+
+        let mut collected = Vec::new();
+        while read_buf.has_remaining() {
+            let chunk = read_buf.chunk().to_vec();
+            read_buf.advance(chunk.len());
+            collected.extend_from_slice(&chunk);
+        }
+
+        read_buf.check().expect("no io::Errors from a slice");
+        assert_eq!(collected, data);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than zero")]
+    fn test_buf_read_buf_rejects_zero_capacity() {
+        let data = b"this data really exists and must not be silently discarded".to_vec();
+        BufReadBuf::with_capacity(&data[..], 0);
+    }
+
+    #[test]
+    fn test_buf_read_buf_with_prost() {
+        let message = MyMsg {
+            a: 1,
+            b: 2,
+            c: "hello world".to_string(),
+        };
+        let mut encoded = vec![];
+        message.encode(&mut encoded).expect("can't run out of memory");
+
+        // Capacity must cover the whole message: `remaining()` can only
+        // report currently-buffered bytes (see the caveat on
+        // `BufReadBuf`), and `prost` bounds-checks length-delimited
+        // fields against it before reading them.
+        let mut read_buf = BufReadBuf::with_capacity(&encoded[..], encoded.len());
+        let decoded = MyMsg::decode(&mut read_buf).expect("decode should succeed");
+        read_buf.check().expect("no io::Errors from a slice");
+
+        assert_eq!(decoded, message);
+    }
+
+    // things to test for BufReadBuf
+    // - reader that returns io::Error
+    // - reader that returns bytes in awkward chunk sizes
+    // - into_inner after partial consumption
+
+    #[test]
+    fn test_unpack_roundtrip() {
+        let mut pack = Pack::new(64);
+        pack.u8(7).unwrap();
+        pack.u16_le(0x1234).unwrap();
+        pack.u32_be(0xdead_beef).unwrap();
+        pack.bytes(b"hi").unwrap();
+        let data = pack.into_inner();
+
+        let mut unpack = Unpack::new(&data);
+        assert_eq!(unpack.u8(), 7);
+        assert_eq!(unpack.u16_le(), 0x1234);
+        assert_eq!(unpack.u32_be(), 0xdead_beef);
+        assert_eq!(unpack.bytes(2), b"hi");
+        unpack.finish().expect("exactly enough bytes were present");
+    }
+
+    #[test]
+    fn test_unpack_truncated_does_not_panic() {
+        let data = [1u8, 2, 3];
+        let mut unpack = Unpack::new(&data);
+
+        assert_eq!(unpack.u8(), 1);
+        assert_eq!(unpack.u32_le(), 0); // not enough bytes left
+        assert!(!unpack.is_ok());
+        assert_eq!(unpack.u8(), 0); // still latched, no panic
+
+        assert_eq!(unpack.finish(), Err(TruncatedError));
+    }
+
+    #[test]
+    fn test_pack_refuses_to_exceed_max_len() {
+        let mut pack = Pack::new(2);
+        pack.u8(1).unwrap();
+        pack.u8(2).unwrap();
+        assert_eq!(
+            pack.u8(3),
+            Err(MessageTooLargeError { max_len: 2 })
+        );
+        assert_eq!(pack.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn test_put_u64_decimal_matches_format() {
+        for v in [0u64, 1, 9, 10, 42, 1000, u64::MAX] {
+            let mut dest = Vec::<u8>::new();
+            let mut write_buf = BufMutWriter::new(&mut dest);
+            write_buf.put_u64_decimal(v);
+            write_buf.close().expect("no io::Errors from Vec<u8>");
+
+            assert_eq!(dest, format!("{v}").into_bytes());
+        }
+    }
+
+    #[test]
+    fn test_put_hex_matches_format() {
+        use std::fmt::Write as _;
+
+        let data = [0x00u8, 0x0f, 0xa5, 0xff];
+
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::new(&mut dest);
+        write_buf.put_hex(&data);
+        write_buf.close().expect("no io::Errors from Vec<u8>");
+
+        let mut expected = String::new();
+        for byte in data {
+            write!(expected, "{byte:02x}").unwrap();
+        }
+        assert_eq!(dest, expected.into_bytes());
+    }
+
+    #[test]
+    fn test_put_hex_with_tiny_capacity_writer() {
+        // Each input byte needs a 2-byte write_with window, which
+        // must not panic even when the writer's capacity is smaller than
+        // that.
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::with_capacity(&mut dest, 1);
+        write_buf.put_hex(&[0xab]);
+        write_buf.close().expect("no io::Errors from Vec<u8>");
+
+        assert_eq!(dest, b"ab");
+    }
+
+    #[test]
+    fn test_put_ascii_matches_format() {
+        let mut dest = Vec::<u8>::new();
+        let mut write_buf = BufMutWriter::new(&mut dest);
+        write_buf.put_ascii("hello world");
+        write_buf.close().expect("no io::Errors from Vec<u8>");
+
+        assert_eq!(dest, b"hello world");
+    }
 }